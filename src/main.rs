@@ -7,13 +7,22 @@ use crossterm::{
     terminal, ExecutableCommand,
 };
 use rand::Rng;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::io::{stdout, Write};
 use std::{
     thread,
     time::{self, Instant},
 };
 
-#[derive(Debug, PartialEq, Eq, Default)]
+// How many pending direction changes a `Snake` remembers between moves. Each
+// move consumes one, so several `change_direction` calls made before a move
+// are applied over successive moves instead of only the last one. The main
+// loop still passes on only the last key read during a tick (see
+// `wait_for_latest_event`), so key presses within one tick collapse to one.
+const MAX_DIR_MEMORY: usize = 10;
+
+#[derive(Debug, PartialEq, Eq, Default, Clone, Hash)]
 struct Point {
     x: u32,
     y: u32,
@@ -27,9 +36,67 @@ enum Direction {
     Right,
 }
 
+impl Direction {
+    fn is_opposite(&self, other: Direction) -> bool {
+        matches!(
+            (self, other),
+            (Direction::Up, Direction::Down)
+                | (Direction::Down, Direction::Up)
+                | (Direction::Left, Direction::Right)
+                | (Direction::Right, Direction::Left)
+        )
+    }
+}
+
+fn step_point(point: &Point, direction: Direction) -> Point {
+    match direction {
+        Direction::Up => Point {
+            x: point.x,
+            y: point.y.saturating_sub(1),
+        },
+        Direction::Down => Point {
+            x: point.x,
+            y: point.y.saturating_add(1),
+        },
+        Direction::Left => Point {
+            x: point.x.saturating_sub(1),
+            y: point.y,
+        },
+        Direction::Right => Point {
+            x: point.x.saturating_add(1),
+            y: point.y,
+        },
+    }
+}
+
+fn manhattan_distance(a: &Point, b: &Point) -> u32 {
+    (a.x as i64 - b.x as i64).unsigned_abs() as u32 + (a.y as i64 - b.y as i64).unsigned_abs() as u32
+}
+
+// A node on the A* open set, ordered so that `BinaryHeap` (a max-heap) pops
+// the lowest `f = g + h` first.
+#[derive(Debug, PartialEq, Eq)]
+struct AStarNode {
+    position: Point,
+    f: u32,
+}
+
+impl Ord for AStarNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.cmp(&self.f)
+    }
+}
+
+impl PartialOrd for AStarNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 struct Snake {
     body: Vec<Point>,
     direction: Direction,
+    pending_directions: VecDeque<Direction>,
     longer_on_next_move: bool,
 }
 
@@ -45,11 +112,15 @@ impl Snake {
         Snake {
             body,
             direction: Direction::Right,
+            pending_directions: VecDeque::new(),
             longer_on_next_move: false,
         }
     }
 
     fn move_to_next_position(&mut self) {
+        if let Some(next_direction) = self.pending_directions.pop_front() {
+            self.direction = next_direction;
+        }
         let head = self.body.last().unwrap();
         let updated_head = match self.direction {
             Direction::Up => Point {
@@ -77,7 +148,18 @@ impl Snake {
     }
 
     fn change_direction(&mut self, direction: Direction) {
-        self.direction = direction;
+        let last_accepted = self
+            .pending_directions
+            .back()
+            .copied()
+            .unwrap_or(self.direction);
+        if last_accepted.is_opposite(direction) {
+            return;
+        }
+        if self.pending_directions.len() >= MAX_DIR_MEMORY {
+            return;
+        }
+        self.pending_directions.push_back(direction);
     }
 
     fn get_body(&self) -> &Vec<Point> {
@@ -116,14 +198,31 @@ impl Snake {
         let head = self.body.last().unwrap();
         head.x <= 0 || head.y <= 0 || head.x >= snake_pit.width || head.y >= snake_pit.height
     }
+
+    fn collides_with_walls(&self, snake_pit: &SnakePit) -> bool {
+        snake_pit.is_wall(self.body.last().unwrap())
+    }
 }
 
 struct SnakePit {
     height: u32,
     width: u32,
+    walls: HashSet<Point>,
 }
 
 impl SnakePit {
+    fn new(height: u32, width: u32) -> SnakePit {
+        Self::new_with_walls(height, width, HashSet::new())
+    }
+
+    fn new_with_walls(height: u32, width: u32, walls: HashSet<Point>) -> SnakePit {
+        SnakePit {
+            height,
+            width,
+            walls,
+        }
+    }
+
     fn get_perimeter(&self) -> Vec<Point> {
         let mut perimeter = Vec::<Point>::new();
         perimeter.try_reserve_exact((2 * self.width + 2 * self.height - 4) as usize);
@@ -142,12 +241,22 @@ impl SnakePit {
         }
         perimeter
     }
+
+    fn is_wall(&self, point: &Point) -> bool {
+        self.walls.contains(point)
+    }
 }
 
+// How many snacks it takes to advance to the next level.
+const SNACKS_PER_LEVEL: u32 = 3;
+const SCORE_PER_SNACK: i32 = 10;
+
 struct SnakeEngine {
     snake: Snake,
     snake_pit: SnakePit,
     snack_position: Point,
+    score: i32,
+    snacks_eaten: u32,
 }
 
 #[derive(Debug, PartialEq)]
@@ -166,16 +275,36 @@ impl SnakeEngine {
         snake_pit_width: u32,
         snake_length: u32,
     ) -> SnakeEngine {
-        let snake_pit = SnakePit {
-            height: snake_pit_height,
-            width: snake_pit_width,
-        };
+        let snake_pit = SnakePit::new(snake_pit_height, snake_pit_width);
         let snake = Snake::new(snake_length, Point { x: 2, y: 2 });
         if let Some(snack_position) = Self::generate_snack(&snake_pit, &snake) {
             SnakeEngine {
                 snake,
                 snake_pit,
                 snack_position,
+                score: 0,
+                snacks_eaten: 0,
+            }
+        } else {
+            panic!("Not able to create SnakeEngine, impossible to generate snack!");
+        }
+    }
+
+    /// Builds a `SnakeEngine` from a text-grid level (see `load_level`),
+    /// falling back to a random snack position if the level has none.
+    fn from_level(level_text: &str) -> SnakeEngine {
+        let level = load_level(level_text)
+            .expect("Invalid level: snake start overlaps a wall or the pit bounds");
+        let snack_position = level
+            .snack_position
+            .or_else(|| Self::generate_snack(&level.snake_pit, &level.snake));
+        if let Some(snack_position) = snack_position {
+            SnakeEngine {
+                snake: level.snake,
+                snake_pit: level.snake_pit,
+                snack_position,
+                score: 0,
+                snacks_eaten: 0,
             }
         } else {
             panic!("Not able to create SnakeEngine, impossible to generate snack!");
@@ -186,18 +315,133 @@ impl SnakeEngine {
         self.snake.change_direction(direction);
     }
 
+    fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    fn get_level(&self) -> i32 {
+        (self.snacks_eaten / SNACKS_PER_LEVEL) as i32
+    }
+
     fn tick(&mut self) -> GameStatus {
         self.snake.move_to_next_position();
-        if self.snake.collides_with_bounds(&self.snake_pit) || self.snake.is_eating_itself() {
+        if self.snake.collides_with_bounds(&self.snake_pit)
+            || self.snake.collides_with_walls(&self.snake_pit)
+            || self.snake.is_eating_itself()
+        {
             return GameStatus::Finished;
         }
         if self.snake.is_eating_snack(&self.snack_position) {
             self.snake.make_longer();
+            self.score += SCORE_PER_SNACK;
+            self.snacks_eaten += 1;
             if let Some(snack_position) = Self::generate_snack(&self.snake_pit, &self.snake) {
                 self.snack_position = snack_position;
             }
         }
-        return GameStatus::ContinueAtLevel(0);
+        return GameStatus::ContinueAtLevel(self.get_level());
+    }
+
+    /// Steers the snake towards the snack with A* and then advances the
+    /// game by one tick, for demoing a self-playing solver.
+    fn step_autopilot(&mut self) -> GameStatus {
+        let direction = self
+            .find_path_to_snack()
+            .unwrap_or_else(|| self.survival_direction());
+        self.change_snake_direction(direction);
+        self.tick()
+    }
+
+    /// Runs A* over the `width x height` grid from the snake's head to
+    /// `snack_position`, treating the snake's body as blocked, and returns
+    /// the `Direction` of the first step of the shortest path, if any.
+    fn find_path_to_snack(&self) -> Option<Direction> {
+        let start = self.snake.body.last().unwrap();
+        let goal = &self.snack_position;
+
+        let mut open_set = BinaryHeap::new();
+        let mut g_score = HashMap::new();
+        let mut came_from: HashMap<(u32, u32), ((u32, u32), Direction)> = HashMap::new();
+        let mut visited = HashSet::new();
+
+        let start_key = (start.x, start.y);
+        g_score.insert(start_key, 0u32);
+        open_set.push(AStarNode {
+            position: Point {
+                x: start.x,
+                y: start.y,
+            },
+            f: manhattan_distance(start, goal),
+        });
+
+        while let Some(AStarNode { position, .. }) = open_set.pop() {
+            let current_key = (position.x, position.y);
+            if position == *goal {
+                let mut key = current_key;
+                let mut first_direction = None;
+                while let Some(&(prev_key, direction)) = came_from.get(&key) {
+                    first_direction = Some(direction);
+                    key = prev_key;
+                }
+                return first_direction;
+            }
+            if !visited.insert(current_key) {
+                continue;
+            }
+            let current_g = g_score[&current_key];
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                let neighbor = step_point(&position, direction);
+                if !self.is_traversable(&neighbor) || self.snake.collides_with_point(&neighbor) {
+                    continue;
+                }
+                let neighbor_key = (neighbor.x, neighbor.y);
+                let tentative_g = current_g + 1;
+                if tentative_g < *g_score.get(&neighbor_key).unwrap_or(&u32::MAX) {
+                    g_score.insert(neighbor_key, tentative_g);
+                    came_from.insert(neighbor_key, (current_key, direction));
+                    open_set.push(AStarNode {
+                        f: tentative_g + manhattan_distance(&neighbor, goal),
+                        position: neighbor,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Picks any direction that keeps the head in-bounds, off walls and off
+    /// the body, used when no path to the snack exists. If every neighbour is
+    /// blocked, returns the current direction.
+    fn survival_direction(&self) -> Direction {
+        let head = self.snake.body.last().unwrap();
+        for direction in [
+            self.snake.direction,
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            let candidate = step_point(head, direction);
+            if self.is_traversable(&candidate) && !self.snake.collides_with_point(&candidate) {
+                return direction;
+            }
+        }
+        self.snake.direction
+    }
+
+    /// In-bounds and not a wall cell — a point the snake could legally move
+    /// onto (body collisions are checked separately by callers).
+    fn is_traversable(&self, point: &Point) -> bool {
+        point.x > 0
+            && point.y > 0
+            && point.x < self.snake_pit.width
+            && point.y < self.snake_pit.height
+            && !self.snake_pit.is_wall(point)
     }
 
     fn generate_snack(snake_pit: &SnakePit, snake: &Snake) -> Option<Point> {
@@ -205,7 +449,8 @@ impl SnakeEngine {
         for x in 1..snake_pit.width {
             for y in 1..snake_pit.height {
                 let possible_position = Point { x, y };
-                if !snake.collides_with_point(&possible_position) {
+                if !snake.collides_with_point(&possible_position) && !snake_pit.is_wall(&possible_position)
+                {
                     possible_snacks.push(possible_position);
                 }
             }
@@ -220,6 +465,65 @@ impl SnakeEngine {
     }
 }
 
+// A level loaded from a text-grid map, ready to seed a `SnakeEngine`.
+struct Level {
+    snake_pit: SnakePit,
+    snake: Snake,
+    snack_position: Option<Point>,
+}
+
+/// Parses a text-grid level map into a `Level`: `#` is a wall, `S` marks
+/// the snake's starting cell, `@` marks the snack, and spaces are empty
+/// floor. The grid's line count and longest line become the pit's height
+/// and width. Returns `None` if the 3-cell snake spawned at `S` would start
+/// out of bounds or overlapping a wall, rather than trusting the map author
+/// left it room.
+fn load_level(level_text: &str) -> Option<Level> {
+    let mut walls = HashSet::new();
+    let mut snake_origin = Point { x: 2, y: 2 };
+    let mut snack_position = None;
+    let mut width = 0;
+    let mut height = 0;
+
+    for (y, line) in level_text.lines().enumerate() {
+        height = height.max(y as u32 + 1);
+        for (x, cell) in line.chars().enumerate() {
+            width = width.max(x as u32 + 1);
+            let point = Point {
+                x: x as u32,
+                y: y as u32,
+            };
+            match cell {
+                '#' => {
+                    walls.insert(point);
+                }
+                'S' => snake_origin = point,
+                '@' => snack_position = Some(point),
+                _ => (),
+            }
+        }
+    }
+
+    let snake_pit = SnakePit::new_with_walls(height, width, walls);
+    let snake = Snake::new(3, snake_origin);
+    let spawn_is_clear = snake.body.iter().all(|point| {
+        point.x > 0
+            && point.y > 0
+            && point.x < snake_pit.width
+            && point.y < snake_pit.height
+            && !snake_pit.is_wall(point)
+    });
+    if !spawn_is_clear {
+        return None;
+    }
+
+    Some(Level {
+        snake_pit,
+        snake,
+        snack_position,
+    })
+}
+
 fn clear_display() {
     stdout().execute(terminal::Clear(terminal::ClearType::All));
 }
@@ -235,7 +539,11 @@ fn display_snake_pit(snake_pit: &SnakePit) {
         } else {
             print!("#");
             for x in 1..snake_pit.width - 1 {
-                print!(" ");
+                if snake_pit.is_wall(&Point { x, y }) {
+                    print!("#");
+                } else {
+                    print!(" ");
+                }
             }
             print!("#\n");
         }
@@ -259,8 +567,172 @@ fn display_snack(snack_position: &Point) {
     stdout.execute(Print("#"));
 }
 
+// Score board, drawn in its own column to the right of the pit so it never
+// overlaps the playing field.
+const SCORE_PANEL_MARGIN: u16 = 2;
+
+/// The handful of running totals a renderer might want to show alongside
+/// the pit, kept separate from `SnakeEngine` so `Renderer` doesn't need to
+/// know about the engine itself.
+struct Hud {
+    score: i32,
+    level: i32,
+    snake_length: usize,
+    pit_width: u32,
+}
+
+/// Decouples `SnakeEngine` from any one presentation technology. A frame is
+/// `clear`, a `draw_*` call per layer, then `present` to flush it.
+trait Renderer {
+    fn clear(&mut self);
+    fn draw_pit(&mut self, snake_pit: &SnakePit);
+    fn draw_snake(&mut self, snake: &Snake);
+    fn draw_snack(&mut self, snack_position: &Point);
+    fn draw_hud(&mut self, hud: &Hud);
+    fn present(&mut self);
+}
+
+struct TerminalRenderer;
+
+impl Renderer for TerminalRenderer {
+    fn clear(&mut self) {
+        clear_display();
+    }
+
+    fn draw_pit(&mut self, snake_pit: &SnakePit) {
+        display_snake_pit(snake_pit);
+    }
+
+    fn draw_snake(&mut self, snake: &Snake) {
+        display_snake(&snake.body);
+    }
+
+    fn draw_snack(&mut self, snack_position: &Point) {
+        display_snack(snack_position);
+    }
+
+    fn draw_hud(&mut self, hud: &Hud) {
+        let panel_x = hud.pit_width as u16 + SCORE_PANEL_MARGIN;
+        let mut stdout = stdout();
+        stdout.execute(cursor::MoveTo(panel_x, 0));
+        stdout.execute(Print(format!("Score: {}", hud.score)));
+        stdout.execute(cursor::MoveTo(panel_x, 1));
+        stdout.execute(Print(format!("Level: {}", hud.level)));
+        stdout.execute(cursor::MoveTo(panel_x, 2));
+        stdout.execute(Print(format!("Length: {}", hud.snake_length)));
+    }
+
+    fn present(&mut self) {
+        stdout().flush().ok();
+    }
+}
+
+// Pixel size of one grid cell in the windowed backend.
+const CELL_PIXELS: usize = 20;
+const COLOR_BACKGROUND: u32 = 0x10_10_10;
+const COLOR_WALL: u32 = 0x44_44_44;
+const COLOR_SNAKE_BODY: u32 = 0x00_aa_00;
+const COLOR_SNAKE_HEAD: u32 = 0x00_ff_00;
+const COLOR_SNACK: u32 = 0xff_00_00;
+
+/// Windowed backend: renders the pit, snake, and snack as filled colored
+/// squares on a grid scaled up by `CELL_PIXELS`, matching the grid-to-pixel
+/// mapping used by graphical snake demos.
+struct WindowRenderer {
+    window: minifb::Window,
+    framebuffer: Vec<u32>,
+    width_px: usize,
+    height_px: usize,
+}
+
+impl WindowRenderer {
+    fn new(snake_pit: &SnakePit) -> WindowRenderer {
+        let width_px = snake_pit.width as usize * CELL_PIXELS;
+        let height_px = snake_pit.height as usize * CELL_PIXELS;
+        let window = minifb::Window::new(
+            "Snake",
+            width_px,
+            height_px,
+            minifb::WindowOptions::default(),
+        )
+        .expect("unable to open window");
+        WindowRenderer {
+            window,
+            framebuffer: vec![COLOR_BACKGROUND; width_px * height_px],
+            width_px,
+            height_px,
+        }
+    }
+
+    fn fill_cell(&mut self, point: &Point, color: u32) {
+        let origin_x = point.x as usize * CELL_PIXELS;
+        let origin_y = point.y as usize * CELL_PIXELS;
+        for y in origin_y..(origin_y + CELL_PIXELS).min(self.height_px) {
+            for x in origin_x..(origin_x + CELL_PIXELS).min(self.width_px) {
+                self.framebuffer[y * self.width_px + x] = color;
+            }
+        }
+    }
+}
+
+impl Renderer for WindowRenderer {
+    fn clear(&mut self) {
+        self.framebuffer.fill(COLOR_BACKGROUND);
+    }
+
+    fn draw_pit(&mut self, snake_pit: &SnakePit) {
+        for x in 0..snake_pit.width {
+            self.fill_cell(&Point { x, y: 0 }, COLOR_WALL);
+            self.fill_cell(
+                &Point {
+                    x,
+                    y: snake_pit.height - 1,
+                },
+                COLOR_WALL,
+            );
+        }
+        for y in 0..snake_pit.height {
+            self.fill_cell(&Point { x: 0, y }, COLOR_WALL);
+            self.fill_cell(
+                &Point {
+                    x: snake_pit.width - 1,
+                    y,
+                },
+                COLOR_WALL,
+            );
+        }
+        for wall in &snake_pit.walls {
+            self.fill_cell(wall, COLOR_WALL);
+        }
+    }
+
+    fn draw_snake(&mut self, snake: &Snake) {
+        for point in snake.get_body().iter() {
+            self.fill_cell(point, COLOR_SNAKE_BODY);
+        }
+        if let Some(head) = snake.get_body().last() {
+            self.fill_cell(head, COLOR_SNAKE_HEAD);
+        }
+    }
+
+    fn draw_snack(&mut self, snack_position: &Point) {
+        self.fill_cell(snack_position, COLOR_SNACK);
+    }
+
+    // minifb has no built-in text rendering, so the HUD has nothing to draw
+    // onto the pixel grid yet; the score/level/length still reach the
+    // renderer, they're just not shown.
+    fn draw_hud(&mut self, _hud: &Hud) {}
+
+    fn present(&mut self) {
+        self.window
+            .update_with_buffer(&self.framebuffer, self.width_px, self.height_px)
+            .ok();
+    }
+}
+
 fn wait_for_latest_event(timeout: u32) -> Option<event::KeyCode> {
-    let limit = Instant::now() + time::Duration::from_millis(1000);
+    let limit = Instant::now() + time::Duration::from_millis(timeout as u64);
     let mut latest_event: Option<event::KeyCode> = None;
     while limit - Instant::now() > time::Duration::from_millis(0) {
         if event::poll(limit - Instant::now()).unwrap() {
@@ -273,15 +745,45 @@ fn wait_for_latest_event(timeout: u32) -> Option<event::KeyCode> {
     latest_event
 }
 
+// Tick speed ramp: each level shaves time off the input timeout, down to a
+// floor so the game never becomes unplayable.
+const BASE_TICK_TIMEOUT_MS: u32 = 1000;
+const TICK_TIMEOUT_STEP_MS: u32 = 75;
+const MIN_TICK_TIMEOUT_MS: u32 = 250;
+
+fn tick_timeout_for_level(level: i32) -> u32 {
+    BASE_TICK_TIMEOUT_MS
+        .saturating_sub(level as u32 * TICK_TIMEOUT_STEP_MS)
+        .max(MIN_TICK_TIMEOUT_MS)
+}
+
+// Picks the presentation backend at startup: `SNAKE_RENDERER=window` opens
+// a `minifb` window, anything else (including unset) keeps the terminal UI.
+fn select_renderer(snake_pit: &SnakePit) -> Box<dyn Renderer> {
+    match std::env::var("SNAKE_RENDERER").as_deref() {
+        Ok("window") => Box::new(WindowRenderer::new(snake_pit)),
+        _ => Box::new(TerminalRenderer),
+    }
+}
+
 fn main() {
     let mut snake_engine = SnakeEngine::new(20, 30);
+    let mut renderer = select_renderer(&snake_engine.snake_pit);
 
     loop {
-        clear_display();
-        display_snake_pit(&snake_engine.snake_pit);
-        display_snack(&snake_engine.snack_position);
-        display_snake(&snake_engine.snake.body);
-        let event = wait_for_latest_event(1000);
+        renderer.clear();
+        renderer.draw_pit(&snake_engine.snake_pit);
+        renderer.draw_snack(&snake_engine.snack_position);
+        renderer.draw_snake(&snake_engine.snake);
+        renderer.draw_hud(&Hud {
+            score: snake_engine.get_score(),
+            level: snake_engine.get_level(),
+            snake_length: snake_engine.snake.get_body().len(),
+            pit_width: snake_engine.snake_pit.width,
+        });
+        renderer.present();
+        let timeout = tick_timeout_for_level(snake_engine.get_level());
+        let event = wait_for_latest_event(timeout);
         match event {
             Some(event::KeyCode::Up) => snake_engine.change_snake_direction(Direction::Up),
             Some(event::KeyCode::Left) => snake_engine.change_snake_direction(Direction::Left),
@@ -291,7 +793,8 @@ fn main() {
         }
         match snake_engine.tick() {
             GameStatus::Finished => {
-                clear_display();
+                renderer.clear();
+                renderer.present();
                 println!("The game has finished!");
                 return;
             }
@@ -478,6 +981,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn direction_queue_buffers_bursts_across_ticks() {
+        let mut snake = Snake::new(3, Point { x: 2, y: 2 });
+
+        snake.change_direction(Direction::Up);
+        snake.change_direction(Direction::Left);
+
+        assert_eq!(snake.direction, Direction::Right);
+        snake.move_to_next_position();
+        assert_eq!(snake.direction, Direction::Up);
+        snake.move_to_next_position();
+        assert_eq!(snake.direction, Direction::Left);
+    }
+
+    #[test]
+    fn direction_queue_rejects_instant_reversal() {
+        let mut snake = Snake::new(3, Point { x: 2, y: 2 });
+
+        snake.change_direction(Direction::Left);
+        snake.move_to_next_position();
+
+        assert_eq!(snake.direction, Direction::Right);
+    }
+
+    #[test]
+    fn direction_queue_rejects_reversal_queued_behind_a_turn() {
+        let mut snake = Snake::new(3, Point { x: 2, y: 2 });
+
+        snake.change_direction(Direction::Up);
+        snake.change_direction(Direction::Down);
+
+        snake.move_to_next_position();
+        assert_eq!(snake.direction, Direction::Up);
+        snake.move_to_next_position();
+        assert_eq!(snake.direction, Direction::Up);
+    }
+
+    #[test]
+    fn direction_queue_is_capped_at_max_dir_memory() {
+        let mut snake = Snake::new(3, Point { x: 2, y: 2 });
+
+        for _ in 0..MAX_DIR_MEMORY + 5 {
+            snake.change_direction(Direction::Up);
+            snake.change_direction(Direction::Right);
+        }
+
+        assert_eq!(snake.pending_directions.len(), MAX_DIR_MEMORY);
+    }
+
     #[test]
     fn snake_collides_with_point() {
         let mut snake = Snake::new(3, Point { x: 3, y: 3 });
@@ -490,40 +1042,29 @@ mod tests {
     #[test]
     fn snake_collides_with_pit() {
         let mut snake = Snake::new(3, Point { x: 3, y: 3 });
-        assert_eq!(
-            snake.collides_with_bounds(&SnakePit {
-                width: 5,
-                height: 10
-            }),
-            true
-        );
+        assert_eq!(snake.collides_with_bounds(&SnakePit::new(10, 5)), true);
 
         let mut snake = Snake::new(3, Point { x: 3, y: 3 });
-        assert_eq!(
-            snake.collides_with_bounds(&SnakePit {
-                width: 10,
-                height: 3
-            }),
-            true
-        );
+        assert_eq!(snake.collides_with_bounds(&SnakePit::new(3, 10)), true);
 
         let mut snake = Snake::new(1, Point { x: 0, y: 3 });
-        assert_eq!(
-            snake.collides_with_bounds(&SnakePit {
-                width: 5,
-                height: 10
-            }),
-            true
-        );
+        assert_eq!(snake.collides_with_bounds(&SnakePit::new(10, 5)), true);
 
         let mut snake = Snake::new(3, Point { x: 3, y: 0 });
-        assert_eq!(
-            snake.collides_with_bounds(&SnakePit {
-                width: 5,
-                height: 10
-            }),
-            true
-        );
+        assert_eq!(snake.collides_with_bounds(&SnakePit::new(10, 5)), true);
+    }
+
+    #[test]
+    fn snake_collides_with_walls() {
+        let mut walls = HashSet::new();
+        walls.insert(Point { x: 5, y: 3 });
+        let snake_pit = SnakePit::new_with_walls(10, 10, walls);
+
+        let mut snake = Snake::new(3, Point { x: 2, y: 3 });
+        assert_eq!(snake.collides_with_walls(&snake_pit), false);
+
+        snake.move_to_next_position();
+        assert_eq!(snake.collides_with_walls(&snake_pit), true);
     }
 
     #[test]
@@ -549,6 +1090,164 @@ mod tests {
         assert_eq!(engine.tick(), GameStatus::ContinueAtLevel(0));
     }
 
+    #[test]
+    fn load_level_parses_walls_snake_start_and_snack() {
+        let level_text = "\
+######
+#S  @#
+#    #
+######";
+
+        let level = load_level(level_text).unwrap();
+
+        assert_eq!(level.snake_pit.height, 4);
+        assert_eq!(level.snake_pit.width, 6);
+        assert!(level.snake_pit.is_wall(&Point { x: 0, y: 0 }));
+        assert!(level.snake_pit.is_wall(&Point { x: 5, y: 1 }));
+        assert!(!level.snake_pit.is_wall(&Point { x: 2, y: 1 }));
+        assert_eq!(level.snake.body.first(), Some(&Point { x: 1, y: 1 }));
+        assert_eq!(level.snack_position, Some(Point { x: 4, y: 1 }));
+    }
+
+    #[test]
+    fn load_level_rejects_snake_start_that_overlaps_a_wall() {
+        // S sits right next to the right wall, so the 3-cell snake spawned
+        // rightwards from it would start on top of (and past) that wall.
+        let level_text = "\
+######
+#   S#
+#    #
+######";
+
+        assert!(load_level(level_text).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid level")]
+    fn engine_from_level_panics_on_an_invalid_spawn() {
+        let level_text = "\
+######
+#   S#
+######";
+        SnakeEngine::from_level(level_text);
+    }
+
+    #[test]
+    fn engine_from_level_dies_on_interior_wall() {
+        let level_text = "\
+######
+#S   #
+# ## #
+#   @#
+######";
+        let mut engine = SnakeEngine::from_level(level_text);
+
+        // The 3-cell snake already spans (1,1)-(3,1), so its head sits
+        // right above the interior wall at (3,2).
+        engine.change_snake_direction(Direction::Down);
+
+        assert_eq!(engine.tick(), GameStatus::Finished);
+    }
+
+    #[test]
+    fn eating_snacks_increases_score_and_level() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        assert_eq!(engine.get_score(), 0);
+        assert_eq!(engine.get_level(), 0);
+
+        for _ in 0..SNACKS_PER_LEVEL {
+            engine.snack_position = Point {
+                x: engine.snake.body.last().unwrap().x + 1,
+                y: engine.snake.body.last().unwrap().y,
+            };
+            assert_ne!(engine.tick(), GameStatus::Finished);
+        }
+
+        assert_eq!(engine.get_score(), SCORE_PER_SNACK * SNACKS_PER_LEVEL as i32);
+        assert_eq!(engine.get_level(), 1);
+    }
+
+    #[test]
+    fn tick_timeout_shortens_as_level_increases() {
+        assert_eq!(tick_timeout_for_level(0), BASE_TICK_TIMEOUT_MS);
+        assert!(tick_timeout_for_level(1) < tick_timeout_for_level(0));
+        assert_eq!(tick_timeout_for_level(100), MIN_TICK_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn autopilot_finds_direction_towards_snack() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        engine.snack_position = Point { x: 2, y: 5 };
+
+        let direction = engine.find_path_to_snack();
+
+        assert_eq!(direction, Some(Direction::Down));
+    }
+
+    #[test]
+    fn autopilot_path_never_crosses_a_wall() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        let mut walls = HashSet::new();
+        for wall in [
+            Point { x: 3, y: 4 },
+            Point { x: 5, y: 4 },
+            Point { x: 4, y: 3 },
+            Point { x: 4, y: 5 },
+        ] {
+            walls.insert(wall);
+        }
+        engine.snake_pit = SnakePit::new_with_walls(10, 10, walls);
+        engine.snack_position = Point { x: 4, y: 4 };
+
+        assert_eq!(engine.find_path_to_snack(), None);
+    }
+
+    #[test]
+    fn survival_direction_never_steps_onto_a_wall() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        let mut walls = HashSet::new();
+        walls.insert(Point { x: 5, y: 2 });
+        engine.snake_pit = SnakePit::new_with_walls(10, 10, walls);
+
+        let direction = engine.survival_direction();
+
+        assert_ne!(direction, Direction::Right);
+        let head = engine.snake.body.last().unwrap();
+        let candidate = step_point(head, direction);
+        assert!(engine.is_traversable(&candidate));
+        assert!(!engine.snake.collides_with_point(&candidate));
+    }
+
+    #[test]
+    fn autopilot_falls_back_to_survival_direction_when_snack_unreachable() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        engine.snack_position = Point { x: 50, y: 50 };
+
+        assert_eq!(engine.find_path_to_snack(), None);
+        let direction = engine.survival_direction();
+        let head = engine.snake.body.last().unwrap();
+        let candidate = step_point(head, direction);
+        assert!(engine.is_traversable(&candidate));
+        assert!(!engine.snake.collides_with_point(&candidate));
+    }
+
+    #[test]
+    fn autopilot_step_moves_snake_and_eventually_eats_snack() {
+        let mut engine = SnakeEngine::new_with_snake_length(10, 10, 3);
+        engine.snack_position = Point { x: 5, y: 2 };
+
+        let mut status = GameStatus::ContinueAtLevel(0);
+        for _ in 0..20 {
+            status = engine.step_autopilot();
+            if engine.snake.get_body().len() > 3 {
+                break;
+            }
+            assert_ne!(status, GameStatus::Finished);
+        }
+
+        assert!(engine.snake.get_body().len() > 3);
+    }
+
     #[test]
     fn snake_eats_itself() {
         let mut snake = Snake::new(5, Point { x: 3, y: 3 });